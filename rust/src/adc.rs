@@ -0,0 +1,150 @@
+//! ESP32-C3 SAR ADC Driver
+//! Direct register access for the onboard ADC1 (analog-to-digital converter),
+//! following the same bare-metal register-poke style as the i2c/gpio drivers.
+
+use crate::shell;
+use core::ptr::{read_volatile, write_volatile};
+
+// SAR ADC register base address
+const APB_SARADC_BASE: u32 = 0x60040000;
+
+// One-time (software-triggered) sample registers
+const APB_SARADC_ONETIME_SAMPLE_REG: u32 = APB_SARADC_BASE + 0x0008;
+const APB_SARADC_1_DATA_STATUS_REG: u32 = APB_SARADC_BASE + 0x002C;
+
+// APB_SARADC_ONETIME_SAMPLE_REG bit fields
+const ONETIME_START: u32 = 1 << 31;
+const ONETIME_CHANNEL_SHIFT: u32 = 4;
+const ONETIME_ATTEN_SHIFT: u32 = 2;
+
+// APB_SARADC_1_DATA_STATUS_REG bit fields
+const DATA_VALID: u32 = 1 << 16; // conversion-done flag
+const DATA_MASK: u32 = 0x0FFF; // 12-bit result
+
+// 11dB attenuation gives the widest input range (~0-3100mV)
+const ATTEN_11DB: u32 = 3;
+
+// Reference voltage for the 11dB attenuation range, in millivolts
+const VREF_MV: u32 = 3100;
+const ADC_MAX: u32 = 4095; // 12-bit full scale
+
+// Number of usable ADC1 channels on the ESP32-C3
+const ADC_CHANNELS: u8 = 5;
+
+#[inline(always)]
+fn reg_write(addr: u32, val: u32) {
+    unsafe { write_volatile(addr as *mut u32, val) }
+}
+
+#[inline(always)]
+fn reg_read(addr: u32) -> u32 {
+    unsafe { read_volatile(addr as *const u32) }
+}
+
+/// Initialize the SAR ADC and register the `adc` shell command
+pub fn init() {
+    reg_write(APB_SARADC_ONETIME_SAMPLE_REG, 0);
+    shell::register(b"adc\0", cmd_adc);
+}
+
+/// Trigger a one-shot conversion on `channel` and return the raw 12-bit result
+pub fn read_channel(channel: u8) -> u16 {
+    let channel = (channel as u32) & 0x7;
+
+    let cmd = ONETIME_START | (channel << ONETIME_CHANNEL_SHIFT) | (ATTEN_11DB << ONETIME_ATTEN_SHIFT);
+    reg_write(APB_SARADC_ONETIME_SAMPLE_REG, cmd);
+
+    // Poll until the conversion-done flag is set
+    loop {
+        let status = reg_read(APB_SARADC_1_DATA_STATUS_REG);
+        if status & DATA_VALID != 0 {
+            reg_write(APB_SARADC_ONETIME_SAMPLE_REG, 0);
+            return (status & DATA_MASK) as u16;
+        }
+    }
+}
+
+/// Read `channel` and convert the raw 12-bit count to millivolts
+pub fn read_millivolts(channel: u8) -> u16 {
+    raw_to_millivolts(read_channel(channel) as u32)
+}
+
+// Convert a raw 12-bit ADC count to millivolts, shared by `read_millivolts`
+// and callers that already have a raw sample on hand
+fn raw_to_millivolts(raw: u32) -> u16 {
+    ((raw * VREF_MV) / ADC_MAX) as u16
+}
+
+// Convert a small non-negative integer to decimal ASCII digits, written into `out`
+fn write_decimal(out: &mut [u8], pos: &mut usize, mut value: u32) {
+    let start = *pos;
+    if value == 0 {
+        out[*pos] = b'0';
+        *pos += 1;
+        return;
+    }
+    while value > 0 {
+        out[*pos] = b'0' + (value % 10) as u8;
+        *pos += 1;
+        value /= 10;
+    }
+    out[start..*pos].reverse();
+}
+
+// Command: adc <channel> - sample a channel and print raw count + mV
+fn cmd_adc(argc: usize, argv: &[&[u8]]) {
+    if argc < 2 {
+        shell::print("Usage: adc <channel>");
+        return;
+    }
+
+    let arg = argv[1];
+    let mut channel: u32 = 0;
+    for &c in arg {
+        if c == 0 {
+            break;
+        }
+        if !c.is_ascii_digit() {
+            shell::print("Usage: adc <channel>");
+            return;
+        }
+
+        channel = match channel
+            .checked_mul(10)
+            .and_then(|v| v.checked_add((c - b'0') as u32))
+        {
+            Some(v) if v < ADC_CHANNELS as u32 => v,
+            _ => {
+                shell::print("Channel out of range (0-4)");
+                return;
+            }
+        };
+    }
+
+    let channel = channel as u8;
+    let raw = read_channel(channel);
+    let mv = raw_to_millivolts(raw as u32);
+
+    let mut line: [u8; 32] = [0; 32];
+    let mut pos = 0;
+    line[pos] = b'c';
+    line[pos + 1] = b'h';
+    pos += 2;
+    write_decimal(&mut line, &mut pos, channel as u32);
+    line[pos] = b':';
+    line[pos + 1] = b' ';
+    pos += 2;
+    write_decimal(&mut line, &mut pos, raw as u32);
+    line[pos] = b' ';
+    line[pos + 1] = b'(';
+    pos += 2;
+    write_decimal(&mut line, &mut pos, mv as u32);
+    line[pos] = b'm';
+    line[pos + 1] = b'V';
+    line[pos + 2] = b')';
+    pos += 3;
+    line[pos] = 0;
+
+    let len = pos;
+    shell::print(unsafe { core::str::from_utf8_unchecked(&line[..len]) });
+}
@@ -9,9 +9,11 @@ const IO_MUX_BASE: u32 = 0x60009000;
 
 // GPIO registers
 const GPIO_ENABLE_REG: u32 = GPIO_BASE + 0x0020;
+const GPIO_ENABLE_W1TC_REG: u32 = GPIO_BASE + 0x0028;  // Write 1 to clear (disable output)
 const GPIO_OUT_REG: u32 = GPIO_BASE + 0x0004;
 const GPIO_OUT_W1TS_REG: u32 = GPIO_BASE + 0x0008;  // Write 1 to set
 const GPIO_OUT_W1TC_REG: u32 = GPIO_BASE + 0x000C;  // Write 1 to clear
+const GPIO_IN_REG: u32 = GPIO_BASE + 0x003C;
 
 // IO MUX registers (one per GPIO)
 #[inline(always)]
@@ -20,10 +22,23 @@ fn gpio_pin_mux_reg(n: u32) -> u32 {
 }
 
 // IO MUX configuration bits
+const FUN_WPU: u32 = 1 << 7;          // Weak pull-up
+const FUN_WPD: u32 = 1 << 8;          // Weak pull-down
 const FUN_IE: u32 = 1 << 9;           // Input enable
 const FUN_DRV_SHIFT: u32 = 10;        // Drive strength
 const MCU_SEL_SHIFT: u32 = 12;        // Function select
 
+/// Pin mode
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// Driven output, input disabled
+    PushPull,
+    /// Driven output with weak pull-up and input enabled, for bit-banged buses
+    OpenDrain,
+    /// Input only, output disabled
+    Input,
+}
+
 // Register access functions
 #[inline(always)]
 fn reg_write(addr: u32, val: u32) {
@@ -40,12 +55,12 @@ fn reg_set_bit(addr: u32, bit: u32) {
     reg_write(addr, reg_read(addr) | bit);
 }
 
-/// Configure a GPIO pin as output
-pub fn set_output(gpio_num: i32) {
+/// Configure a GPIO pin's mode (push-pull output, open-drain output, or input)
+pub fn set_mode(gpio_num: i32, mode: Mode) {
     if gpio_num < 0 || gpio_num > 21 {
         return;  // ESP32-C3 has GPIO 0-21
     }
-    
+
     let gpio_num = gpio_num as u32;
 
     // Configure IO MUX for GPIO function
@@ -60,10 +75,32 @@ pub fn set_output(gpio_num: i32) {
     mux_val &= !(0x3 << FUN_DRV_SHIFT);
     mux_val |= 2 << FUN_DRV_SHIFT;
 
+    mux_val &= !(FUN_IE | FUN_WPU | FUN_WPD);
+    match mode {
+        Mode::PushPull => {}
+        Mode::OpenDrain => mux_val |= FUN_IE | FUN_WPU,
+        Mode::Input => mux_val |= FUN_IE,
+    }
+
     reg_write(mux_reg, mux_val);
 
-    // Enable output
-    reg_set_bit(GPIO_ENABLE_REG, 1 << gpio_num);
+    match mode {
+        Mode::PushPull | Mode::OpenDrain => reg_set_bit(GPIO_ENABLE_REG, 1 << gpio_num),
+        Mode::Input => reg_write(GPIO_ENABLE_W1TC_REG, 1 << gpio_num),
+    }
+}
+
+/// Configure a GPIO pin as a push-pull output
+pub fn set_output(gpio_num: i32) {
+    set_mode(gpio_num, Mode::PushPull);
+}
+
+/// Read the current level of a GPIO pin
+pub fn read(gpio_num: i32) -> bool {
+    if gpio_num < 0 || gpio_num > 21 {
+        return false;
+    }
+    (reg_read(GPIO_IN_REG) & (1 << gpio_num)) != 0
 }
 
 /// Set GPIO pin high
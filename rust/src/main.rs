@@ -11,9 +11,11 @@
 #![no_std]
 #![no_main]
 
+mod adc;
 mod console;
 mod gpio;
 mod i2c;
+mod spi;
 mod ssd1306;
 mod shell;
 mod font5x7;
@@ -33,9 +35,12 @@ fn main() -> ! {
     // Initialize OLED display
     console::puts("Initializing OLED display...\n");
     let oled_config = ssd1306::Ssd1306Config {
-        i2c_addr: ssd1306::SSD1306_I2C_ADDR_DEFAULT,  // 0x3C
-        scl_pin: 7,   // GPIO7 (SCL/D5 on XIAO ESP32-C3)
-        sda_pin: 6,   // GPIO6 (SDA/D4 on XIAO ESP32-C3)
+        interface: ssd1306::Interface::I2c {
+            addr: ssd1306::SSD1306_I2C_ADDR_DEFAULT,  // 0x3C
+            scl: 7,  // GPIO7 (SCL/D5 on XIAO ESP32-C3)
+            sda: 6,  // GPIO6 (SDA/D4 on XIAO ESP32-C3)
+        },
+        ..Default::default()
     };
 
     if ssd1306::init(&oled_config) {
@@ -48,6 +53,11 @@ fn main() -> ! {
     // Initialize shell
     console::puts("Initializing shell...\n");
     shell::init();
+
+    // Initialize ADC and register its shell command
+    console::puts("Initializing ADC...\n");
+    adc::init();
+
     console::puts("\nShell ready! Type commands in your terminal.\n");
     console::puts("Commands will appear on the OLED display.\n\n");
 
@@ -2,6 +2,7 @@
 //! Reads from USB Serial, displays on OLED
 
 use crate::console;
+use crate::i2c;
 use crate::ssd1306;
 
 // Shell configuration
@@ -12,6 +13,18 @@ pub const SHELL_MAX_ARGS: usize = 8;
 static mut INPUT_BUFFER: [u8; SHELL_MAX_LINE_LENGTH] = [0; SHELL_MAX_LINE_LENGTH];
 static mut INPUT_POS: usize = 0;
 
+// Command history: a small ring buffer, newest entry at index 0
+const HISTORY_SIZE: usize = 8;
+static mut HISTORY: [[u8; SHELL_MAX_LINE_LENGTH]; HISTORY_SIZE] = [[0; SHELL_MAX_LINE_LENGTH]; HISTORY_SIZE];
+static mut HISTORY_COUNT: usize = 0;
+// Position while browsing with up/down; None means the current line is not
+// recalled from history
+static mut HISTORY_NAV: Option<usize> = None;
+
+// Escape-sequence parser state for VT100 arrow keys, carried across calls to
+// process_char(): 0 = idle, 1 = saw ESC, 2 = saw ESC '['
+static mut ESC_STATE: u8 = 0;
+
 // Output line buffer (8 lines on 64-pixel display with 8-pixel font height)
 const MAX_LINES: usize = 8;
 static mut DISPLAY_LINES: [[u8; 22]; MAX_LINES] = [[0; 22]; MAX_LINES];  // 21 chars + null
@@ -59,6 +72,12 @@ fn bytes_to_str(bytes: &[u8]) -> &str {
     unsafe { core::str::from_utf8_unchecked(&bytes[..len]) }
 }
 
+/// Print a line through the shell's output path (serial + OLED), for use by
+/// commands registered from other modules via register()
+pub fn print(text: &str) {
+    shell_print(text);
+}
+
 // Print a line to the display buffer
 fn shell_print(text: &str) {
     // Echo to serial console for debugging
@@ -100,6 +119,60 @@ fn cmd_help(_argc: usize, _argv: &[&[u8]]) {
     shell_print("  help  - Show help");
     shell_print("  clear - Clear screen");
     shell_print("  echo  - Echo text");
+    shell_print("  i2c   - Scan I2C bus");
+}
+
+// Convert a 4-bit value to its lowercase hex digit
+fn hex_digit(n: u8) -> u8 {
+    if n < 10 { b'0' + n } else { b'a' + (n - 10) }
+}
+
+// Command: i2c - scan the bus and report addresses that ACK
+fn cmd_i2c(_argc: usize, _argv: &[&[u8]]) {
+    shell_print("Scanning I2C bus...");
+
+    let mut line: [u8; SHELL_MAX_LINE_LENGTH] = [0; SHELL_MAX_LINE_LENGTH];
+    let mut pos: usize = 0;
+    let mut found = false;
+
+    for addr in 0x03u8..=0x77u8 {
+        if !i2c::start() {
+            continue;
+        }
+        let acked = i2c::write_byte(addr << 1);
+        i2c::stop();
+
+        if !acked {
+            continue;
+        }
+        found = true;
+
+        // Wrap to a new line once the current one is full (21 chars per display line)
+        if pos + 5 > 21 {
+            line[pos] = 0;
+            shell_print(bytes_to_str(&line));
+            pos = 0;
+        }
+
+        if pos > 0 {
+            line[pos] = b' ';
+            pos += 1;
+        }
+        line[pos] = b'0';
+        line[pos + 1] = b'x';
+        line[pos + 2] = hex_digit(addr >> 4);
+        line[pos + 3] = hex_digit(addr & 0x0F);
+        pos += 4;
+    }
+
+    if pos > 0 {
+        line[pos] = 0;
+        shell_print(bytes_to_str(&line));
+    }
+
+    if !found {
+        shell_print("No devices found");
+    }
 }
 
 // Command: clear
@@ -138,17 +211,36 @@ fn cmd_echo(argc: usize, argv: &[&[u8]]) {
 }
 
 // Command table entry
-struct Command {
-    name: &'static [u8],
-    handler: fn(usize, &[&[u8]]),
+#[derive(Clone, Copy)]
+pub struct Command {
+    pub name: &'static [u8],
+    pub handler: fn(usize, &[&[u8]]),
 }
 
-static COMMANDS: [Command; 3] = [
+static COMMANDS: [Command; 4] = [
     Command { name: b"help\0", handler: cmd_help },
     Command { name: b"clear\0", handler: cmd_clear },
     Command { name: b"echo\0", handler: cmd_echo },
+    Command { name: b"i2c\0", handler: cmd_i2c },
 ];
 
+// Commands registered at runtime by other modules via register()
+const MAX_DYNAMIC_COMMANDS: usize = 16;
+static mut DYNAMIC_COMMANDS: [Option<Command>; MAX_DYNAMIC_COMMANDS] = [None; MAX_DYNAMIC_COMMANDS];
+
+/// Register a shell command from another module (e.g. a driver's own init()).
+/// Ignored if the dynamic command table is full.
+pub fn register(name: &'static [u8], handler: fn(usize, &[&[u8]])) {
+    unsafe {
+        for slot in DYNAMIC_COMMANDS.iter_mut() {
+            if slot.is_none() {
+                *slot = Some(Command { name, handler });
+                return;
+            }
+        }
+    }
+}
+
 // Parse command line and execute
 fn shell_execute(cmdline: &[u8]) {
     // Echo the command
@@ -208,7 +300,7 @@ fn shell_execute(cmdline: &[u8]) {
         return;
     }
 
-    // Find and execute command
+    // Find and execute command: built-ins first, then runtime-registered ones
     let mut found = false;
     for cmd in &COMMANDS {
         if str_equals(argv[0], cmd.name) {
@@ -218,6 +310,20 @@ fn shell_execute(cmdline: &[u8]) {
         }
     }
 
+    if !found {
+        unsafe {
+            for slot in DYNAMIC_COMMANDS.iter() {
+                if let Some(cmd) = slot {
+                    if str_equals(argv[0], cmd.name) {
+                        (cmd.handler)(argc, &argv);
+                        found = true;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
     if !found {
         let prefix = "command unknown: ";
         let cmd_str = bytes_to_str(argv[0]);
@@ -242,11 +348,92 @@ fn shell_execute(cmdline: &[u8]) {
     }
 }
 
+// Push a completed command line onto the history ring buffer
+fn history_push(line: &[u8]) {
+    if str_len(line) == 0 {
+        return;
+    }
+
+    unsafe {
+        for i in (1..HISTORY_SIZE).rev() {
+            HISTORY[i] = HISTORY[i - 1];
+        }
+        str_copy(&mut HISTORY[0], line, SHELL_MAX_LINE_LENGTH);
+
+        if HISTORY_COUNT < HISTORY_SIZE {
+            HISTORY_COUNT += 1;
+        }
+        HISTORY_NAV = None;
+    }
+}
+
+// Erase `old_len` echoed characters on the serial line, then re-echo the
+// current INPUT_BUFFER
+fn redraw_input_line(old_len: usize) {
+    unsafe {
+        for _ in 0..old_len {
+            console::putc('\x08');
+            console::putc(' ');
+            console::putc('\x08');
+        }
+        for i in 0..INPUT_POS {
+            console::putc(INPUT_BUFFER[i] as char);
+        }
+    }
+}
+
+// Recall the next-older entry (up arrow)
+fn history_recall_older() {
+    unsafe {
+        if HISTORY_COUNT == 0 {
+            return;
+        }
+
+        let next = match HISTORY_NAV {
+            None => 0,
+            Some(i) if i + 1 < HISTORY_COUNT => i + 1,
+            Some(i) => i,
+        };
+
+        let old_len = INPUT_POS;
+        HISTORY_NAV = Some(next);
+        str_copy(&mut INPUT_BUFFER, &HISTORY[next], SHELL_MAX_LINE_LENGTH);
+        INPUT_POS = str_len(&INPUT_BUFFER);
+        redraw_input_line(old_len);
+    }
+}
+
+// Recall the next-newer entry, or clear the line if already at the newest (down arrow)
+fn history_recall_newer() {
+    unsafe {
+        let old_len = INPUT_POS;
+
+        match HISTORY_NAV {
+            None => return,
+            Some(0) => {
+                HISTORY_NAV = None;
+                INPUT_BUFFER[0] = 0;
+                INPUT_POS = 0;
+            }
+            Some(i) => {
+                HISTORY_NAV = Some(i - 1);
+                str_copy(&mut INPUT_BUFFER, &HISTORY[i - 1], SHELL_MAX_LINE_LENGTH);
+                INPUT_POS = str_len(&INPUT_BUFFER);
+            }
+        }
+
+        redraw_input_line(old_len);
+    }
+}
+
 /// Initialize shell
 pub fn init() {
     unsafe {
         INPUT_POS = 0;
         CURRENT_LINE = 0;
+        HISTORY_COUNT = 0;
+        HISTORY_NAV = None;
+        ESC_STATE = 0;
 
         // Clear display lines
         for i in 0..MAX_LINES {
@@ -265,6 +452,26 @@ pub fn process_char(c: char) {
     let c = c as u8;
 
     unsafe {
+        // Continue parsing a VT100 escape sequence (ESC '[' 'A'/'B') across calls
+        if ESC_STATE == 1 {
+            ESC_STATE = if c == b'[' { 2 } else { 0 };
+            return;
+        }
+        if ESC_STATE == 2 {
+            ESC_STATE = 0;
+            if c == b'A' {
+                history_recall_older();
+            } else if c == b'B' {
+                history_recall_newer();
+            }
+            return;
+        }
+        if c == 0x1B {
+            // ESC
+            ESC_STATE = 1;
+            return;
+        }
+
         // Handle backspace
         if c == 0x08 || c == 127 {  // Backspace or DEL
             if INPUT_POS > 0 {
@@ -283,6 +490,7 @@ pub fn process_char(c: char) {
             INPUT_BUFFER[INPUT_POS] = 0;
 
             if INPUT_POS > 0 {
+                history_push(&INPUT_BUFFER);
                 shell_execute(&INPUT_BUFFER);
             }
 
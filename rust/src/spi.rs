@@ -0,0 +1,90 @@
+//! ESP32-C3 Bit-Banged SPI Driver
+//! Software implementation of a write-only SPI master (mode 0, MSB-first)
+//! for peripherals that only need a clock and MOSI line, such as the
+//! SSD1306 OLED over its 4-wire SPI interface.
+
+use crate::gpio;
+
+// SPI state
+static mut SPI_DELAY_CYCLES: u32 = 0;
+static mut SCK_GPIO: i32 = 0;
+static mut MOSI_GPIO: i32 = 0;
+static mut CS_GPIO: i32 = 0;
+
+/// SPI configuration
+pub struct SpiConfig {
+    pub sck_pin: i32,
+    pub mosi_pin: i32,
+    pub cs_pin: i32,
+    pub freq_hz: u32,
+}
+
+// Delay function for SPI clock timing
+fn spi_delay() {
+    unsafe {
+        for _ in 0..SPI_DELAY_CYCLES {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Initialize SPI master
+pub fn init(config: &SpiConfig) {
+    unsafe {
+        SCK_GPIO = config.sck_pin;
+        MOSI_GPIO = config.mosi_pin;
+        CS_GPIO = config.cs_pin;
+
+        // Calculate delay cycles for desired frequency
+        // ESP32-C3 runs at 160MHz, adjust for desired SPI frequency
+        SPI_DELAY_CYCLES = (160_000_000 / config.freq_hz) / 4;
+    }
+
+    gpio::set_output(config.sck_pin);
+    gpio::set_output(config.mosi_pin);
+    gpio::set_output(config.cs_pin);
+
+    // Idle state: clock low (mode 0), device deselected
+    gpio::set_low(config.sck_pin);
+    gpio::set_high(config.cs_pin);
+}
+
+/// Assert chip-select (active low)
+pub fn select() {
+    unsafe {
+        gpio::set_low(CS_GPIO);
+    }
+}
+
+/// Deassert chip-select
+pub fn deselect() {
+    unsafe {
+        gpio::set_high(CS_GPIO);
+    }
+}
+
+/// Shift out a single byte, MSB first
+pub fn write_byte(data: u8) {
+    unsafe {
+        for i in (0..8).rev() {
+            if (data & (1 << i)) != 0 {
+                gpio::set_high(MOSI_GPIO);
+            } else {
+                gpio::set_low(MOSI_GPIO);
+            }
+            spi_delay();
+            gpio::set_high(SCK_GPIO);
+            spi_delay();
+            gpio::set_low(SCK_GPIO);
+        }
+    }
+}
+
+/// Write multiple bytes as a single chip-select transaction
+pub fn write(data: &[u8]) {
+    select();
+    for &byte in data {
+        write_byte(byte);
+    }
+    deselect();
+}
@@ -11,8 +11,10 @@
 //! - Basic graphics (pixels, rectangles)
 //! - Display control (contrast, invert, on/off)
 
-use crate::i2c;
 use crate::font5x7::FONT5X7;
+use crate::gpio;
+use crate::i2c;
+use crate::spi;
 
 // Display dimensions
 pub const SSD1306_WIDTH: usize = 128;
@@ -41,76 +43,221 @@ const SSD1306_CMD_SET_VCOM_DETECT: u8 = 0xDB;
 const SSD1306_CMD_SET_MULTIPLEX: u8 = 0xA8;
 const SSD1306_CMD_SEG_REMAP: u8 = 0xA0;
 const SSD1306_CMD_COM_SCAN_DEC: u8 = 0xC8;
+const SSD1306_CMD_COM_SCAN_INC: u8 = 0xC0;
 const SSD1306_CMD_CHARGE_PUMP: u8 = 0x8D;
 
 // I²C Control Bytes
 const SSD1306_CONTROL_CMD_SINGLE: u8 = 0x80;
+const SSD1306_CONTROL_CMD_STREAM: u8 = 0x00;
 const SSD1306_CONTROL_DATA_STREAM: u8 = 0x40;
 
 // Display buffer (128x64 = 8192 bits = 1024 bytes)
 static mut SSD1306_BUFFER: [u8; SSD1306_WIDTH * SSD1306_HEIGHT / 8] = [0; SSD1306_WIDTH * SSD1306_HEIGHT / 8];
-static mut SSD1306_I2C_ADDR: u8 = 0;
+
+/// Transport used to reach the panel
+#[derive(Clone, Copy)]
+pub enum Interface {
+    I2c { addr: u8, scl: i32, sda: i32 },
+    Spi { sck: i32, mosi: i32, cs: i32, dc: i32, reset: i32 },
+}
+
+static mut SSD1306_INTERFACE: Interface = Interface::I2c { addr: 0, scl: 0, sda: 0 };
+
+// Per-page dirty tracking so display() only re-streams the pages that changed.
+// The buffer and dirty array are sized for the largest panel this driver
+// supports (128x64); smaller panels just use a prefix of the pages.
+const SSD1306_MAX_PAGES: usize = SSD1306_HEIGHT / 8;
+static mut DIRTY: [bool; SSD1306_MAX_PAGES] = [false; SSD1306_MAX_PAGES];
+
+// Number of pages actually driven, set from Ssd1306Config::height at init()
+static mut PANEL_PAGES: usize = SSD1306_MAX_PAGES;
+
+// Mark a single page dirty
+fn mark_page_dirty(page: usize) {
+    unsafe {
+        if page < SSD1306_MAX_PAGES {
+            DIRTY[page] = true;
+        }
+    }
+}
+
+// Mark all pages dirty
+fn mark_all_dirty() {
+    unsafe {
+        for dirty in DIRTY.iter_mut() {
+            *dirty = true;
+        }
+    }
+}
 
 /// SSD1306 configuration
+#[derive(Clone, Copy)]
 pub struct Ssd1306Config {
-    pub i2c_addr: u8,
-    pub scl_pin: i32,
-    pub sda_pin: i32,
+    pub interface: Interface,
+    /// Panel height in pixels (32 or 64)
+    pub height: usize,
+    /// COM pins hardware config (0x02 sequential, 0x12 alternative)
+    pub com_pins: u8,
+    /// Mirror columns (SEG_REMAP)
+    pub segment_remap: bool,
+    /// Mirror rows (COM_SCAN_DEC vs COM_SCAN_INC)
+    pub com_scan_dec: bool,
+    /// Multiplex ratio, typically height - 1
+    pub mux_ratio: u8,
+    /// Vertical display offset (SET_DISPLAY_OFFSET); the start line stays 0
+    pub display_offset: u8,
+}
+
+impl Default for Ssd1306Config {
+    fn default() -> Self {
+        Ssd1306Config {
+            interface: Interface::I2c { addr: SSD1306_I2C_ADDR_DEFAULT, scl: 0, sda: 0 },
+            height: SSD1306_HEIGHT,
+            com_pins: 0x12,
+            segment_remap: true,
+            com_scan_dec: true,
+            mux_ratio: (SSD1306_HEIGHT - 1) as u8,
+            display_offset: 0x00,
+        }
+    }
 }
 
 // Send command to SSD1306
 fn send_command(cmd: u8) -> bool {
     unsafe {
-        let data = [SSD1306_CONTROL_CMD_SINGLE, cmd];
-        i2c::write(SSD1306_I2C_ADDR, &data)
+        match SSD1306_INTERFACE {
+            Interface::I2c { addr, .. } => {
+                let data = [SSD1306_CONTROL_CMD_SINGLE, cmd];
+                i2c::write(addr, &data)
+            }
+            Interface::Spi { dc, .. } => {
+                gpio::set_low(dc);
+                spi::write(&[cmd]);
+                true
+            }
+        }
     }
 }
 
-// Send data to SSD1306
-fn send_data(data: &[u8]) -> bool {
+// Send a whole stream of commands as a single transaction
+fn send_commands(cmds: &[u8]) -> bool {
     unsafe {
-        if !i2c::start() {
-            return false;
-        }
+        match SSD1306_INTERFACE {
+            Interface::I2c { addr, .. } => {
+                if !i2c::start() {
+                    return false;
+                }
+
+                // Write device address with write bit
+                if !i2c::write_byte(addr << 1) {
+                    i2c::stop();
+                    return false;
+                }
+
+                // Write control byte for command stream
+                if !i2c::write_byte(SSD1306_CONTROL_CMD_STREAM) {
+                    i2c::stop();
+                    return false;
+                }
+
+                // Write command bytes
+                for &cmd in cmds {
+                    if !i2c::write_byte(cmd) {
+                        i2c::stop();
+                        return false;
+                    }
+                }
 
-        // Write device address with write bit
-        if !i2c::write_byte(SSD1306_I2C_ADDR << 1) {
-            i2c::stop();
-            return false;
+                i2c::stop();
+                true
+            }
+            Interface::Spi { dc, .. } => {
+                gpio::set_low(dc);
+                spi::write(cmds);
+                true
+            }
         }
+    }
+}
 
-        // Write control byte for data stream
-        if !i2c::write_byte(SSD1306_CONTROL_DATA_STREAM) {
-            i2c::stop();
-            return false;
-        }
+// Send data to SSD1306
+fn send_data(data: &[u8]) -> bool {
+    unsafe {
+        match SSD1306_INTERFACE {
+            Interface::I2c { addr, .. } => {
+                if !i2c::start() {
+                    return false;
+                }
+
+                // Write device address with write bit
+                if !i2c::write_byte(addr << 1) {
+                    i2c::stop();
+                    return false;
+                }
+
+                // Write control byte for data stream
+                if !i2c::write_byte(SSD1306_CONTROL_DATA_STREAM) {
+                    i2c::stop();
+                    return false;
+                }
+
+                // Write data bytes
+                for &byte in data {
+                    if !i2c::write_byte(byte) {
+                        i2c::stop();
+                        return false;
+                    }
+                }
 
-        // Write data bytes
-        for &byte in data {
-            if !i2c::write_byte(byte) {
                 i2c::stop();
-                return false;
+                true
+            }
+            Interface::Spi { dc, .. } => {
+                gpio::set_high(dc);
+                spi::write(data);
+                true
             }
         }
-
-        i2c::stop();
-        true
     }
 }
 
 /// Initialize display
 pub fn init(config: &Ssd1306Config) -> bool {
     unsafe {
-        SSD1306_I2C_ADDR = config.i2c_addr;
+        SSD1306_INTERFACE = config.interface;
+        PANEL_PAGES = config.height / 8;
     }
 
-    // Initialize I²C peripheral
-    let i2c_cfg = i2c::I2cConfig {
-        scl_pin: config.scl_pin,
-        sda_pin: config.sda_pin,
-        freq_hz: 400000,  // 400kHz (fast mode I²C)
-    };
-    i2c::init(&i2c_cfg);
+    match config.interface {
+        Interface::I2c { scl, sda, .. } => {
+            let i2c_cfg = i2c::I2cConfig {
+                scl_pin: scl,
+                sda_pin: sda,
+                freq_hz: 400000,  // 400kHz (fast mode I²C)
+            };
+            i2c::init(&i2c_cfg);
+        }
+        Interface::Spi { sck, mosi, cs, dc, reset } => {
+            let spi_cfg = spi::SpiConfig {
+                sck_pin: sck,
+                mosi_pin: mosi,
+                cs_pin: cs,
+                freq_hz: 8_000_000,  // 8MHz, well within the SSD1306's SPI limit
+            };
+            spi::init(&spi_cfg);
+
+            gpio::set_output(dc);
+            gpio::set_output(reset);
+
+            // Hardware reset pulse: hold low ~10ms, then release
+            gpio::set_high(reset);
+            gpio::set_low(reset);
+            for _ in 0..1_600_000 {
+                core::hint::spin_loop();
+            }
+            gpio::set_high(reset);
+        }
+    }
 
     // Power-up delay
     for _ in 0..100000 {
@@ -118,31 +265,35 @@ pub fn init(config: &Ssd1306Config) -> bool {
     }
 
     // === SSD1306 Initialization Sequence ===
-    send_command(SSD1306_CMD_DISPLAY_OFF);
-    send_command(SSD1306_CMD_SET_DISPLAY_CLK_DIV);
-    send_command(0x80);
-    send_command(SSD1306_CMD_SET_MULTIPLEX);
-    send_command((SSD1306_HEIGHT - 1) as u8);
-    send_command(SSD1306_CMD_SET_DISPLAY_OFFSET);
-    send_command(0x00);
-    send_command(SSD1306_CMD_SET_START_LINE | 0x00);
-    send_command(SSD1306_CMD_CHARGE_PUMP);
-    send_command(0x14);
-    send_command(SSD1306_CMD_MEMORY_MODE);
-    send_command(0x00);
-    send_command(SSD1306_CMD_SEG_REMAP | 0x01);
-    send_command(SSD1306_CMD_COM_SCAN_DEC);
-    send_command(SSD1306_CMD_SET_COM_PINS);
-    send_command(0x12);
-    send_command(SSD1306_CMD_SET_CONTRAST);
-    send_command(0xCF);
-    send_command(SSD1306_CMD_SET_PRECHARGE);
-    send_command(0xF1);
-    send_command(SSD1306_CMD_SET_VCOM_DETECT);
-    send_command(0x40);
-    send_command(SSD1306_CMD_DISPLAY_ALL_ON_RESUME);
-    send_command(SSD1306_CMD_NORMAL_DISPLAY);
-    send_command(SSD1306_CMD_DISPLAY_ON);
+    // Streamed as a single transaction instead of one start/stop per command
+    let init_cmds = [
+        SSD1306_CMD_DISPLAY_OFF,
+        SSD1306_CMD_SET_DISPLAY_CLK_DIV,
+        0x80,
+        SSD1306_CMD_SET_MULTIPLEX,
+        config.mux_ratio,
+        SSD1306_CMD_SET_DISPLAY_OFFSET,
+        config.display_offset,
+        SSD1306_CMD_SET_START_LINE | 0x00,
+        SSD1306_CMD_CHARGE_PUMP,
+        0x14,
+        SSD1306_CMD_MEMORY_MODE,
+        0x00,
+        SSD1306_CMD_SEG_REMAP | if config.segment_remap { 0x01 } else { 0x00 },
+        if config.com_scan_dec { SSD1306_CMD_COM_SCAN_DEC } else { SSD1306_CMD_COM_SCAN_INC },
+        SSD1306_CMD_SET_COM_PINS,
+        config.com_pins,
+        SSD1306_CMD_SET_CONTRAST,
+        0xCF,
+        SSD1306_CMD_SET_PRECHARGE,
+        0xF1,
+        SSD1306_CMD_SET_VCOM_DETECT,
+        0x40,
+        SSD1306_CMD_DISPLAY_ALL_ON_RESUME,
+        SSD1306_CMD_NORMAL_DISPLAY,
+        SSD1306_CMD_DISPLAY_ON,
+    ];
+    send_commands(&init_cmds);
 
     // Clear display buffer and show blank screen
     clear();
@@ -158,22 +309,40 @@ pub fn clear() {
             *byte = 0;
         }
     }
+    mark_all_dirty();
 }
 
-/// Update the physical display with the current buffer contents
+/// Update the physical display, re-streaming only the pages marked dirty
 pub fn display() {
-    send_command(SSD1306_CMD_COLUMN_ADDR);
-    send_command(0);
-    send_command((SSD1306_WIDTH - 1) as u8);
-    send_command(SSD1306_CMD_PAGE_ADDR);
-    send_command(0);
-    send_command((SSD1306_HEIGHT / 8 - 1) as u8);
-
     unsafe {
-        send_data(&SSD1306_BUFFER);
+        for page in 0..PANEL_PAGES {
+            if !DIRTY[page] {
+                continue;
+            }
+
+            send_commands(&[
+                SSD1306_CMD_COLUMN_ADDR,
+                0,
+                (SSD1306_WIDTH - 1) as u8,
+                SSD1306_CMD_PAGE_ADDR,
+                page as u8,
+                page as u8,
+            ]);
+
+            let start = page * SSD1306_WIDTH;
+            send_data(&SSD1306_BUFFER[start..start + SSD1306_WIDTH]);
+
+            DIRTY[page] = false;
+        }
     }
 }
 
+/// Force a full-buffer refresh, ignoring dirty tracking
+pub fn display_full() {
+    mark_all_dirty();
+    display();
+}
+
 /// Set a single pixel in the display buffer
 pub fn set_pixel(x: i32, y: i32, color: u8) {
     if x < 0 || x >= SSD1306_WIDTH as i32 || y < 0 || y >= SSD1306_HEIGHT as i32 {
@@ -190,6 +359,8 @@ pub fn set_pixel(x: i32, y: i32, color: u8) {
             SSD1306_BUFFER[x + (y / 8) * SSD1306_WIDTH] &= !(1 << (y & 7));
         }
     }
+
+    mark_page_dirty(y / 8);
 }
 
 /// Draw a single character using the 5x7 font
@@ -234,6 +405,52 @@ pub fn draw_string(x: i32, y: i32, s: &str) {
     }
 }
 
+/// Draw a single character using the 5x7 font, each glyph bit rendered as a
+/// `scale x scale` block
+pub fn draw_char_scaled(x: i32, y: i32, c: char, scale: u8) {
+    let c = if c < ' ' || c > '~' { ' ' } else { c };
+    let idx = (c as usize) - 32;
+
+    if idx >= FONT5X7.len() {
+        return;
+    }
+
+    let glyph = &FONT5X7[idx];
+    let scale = scale as i32;
+
+    for i in 0..5 {
+        let line = glyph[i];
+        for j in 0..7 {
+            if (line & (1 << j)) != 0 {
+                fill_rect(x + i as i32 * scale, y + j as i32 * scale, scale, scale, 1);
+            }
+        }
+    }
+}
+
+/// Draw a text string at an integer scale (1x, 2x, 3x, ...)
+pub fn draw_string_scaled(x: i32, y: i32, s: &str, scale: u8) {
+    let mut cursor_x = x;
+    let mut cursor_y = y;
+    let advance_x = 6 * scale as i32;
+    let advance_y = 8 * scale as i32;
+
+    for c in s.chars() {
+        if c == '\n' {
+            cursor_x = x;
+            cursor_y += advance_y;
+        } else {
+            draw_char_scaled(cursor_x, cursor_y, c, scale);
+            cursor_x += advance_x;
+
+            if cursor_x >= SSD1306_WIDTH as i32 {
+                cursor_x = x;
+                cursor_y += advance_y;
+            }
+        }
+    }
+}
+
 /// Draw a filled rectangle
 pub fn fill_rect(x: i32, y: i32, w: i32, h: i32, color: u8) {
     for i in x..(x + w) {
@@ -243,10 +460,131 @@ pub fn fill_rect(x: i32, y: i32, w: i32, h: i32, color: u8) {
     }
 }
 
+/// Draw a line using Bresenham's algorithm
+pub fn draw_line(x0: i32, y0: i32, x1: i32, y1: i32, color: u8) {
+    let mut x0 = x0;
+    let mut y0 = y0;
+
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    loop {
+        set_pixel(x0, y0, color);
+
+        if x0 == x1 && y0 == y1 {
+            break;
+        }
+
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y0 += sy;
+        }
+    }
+}
+
+/// Draw a rectangle outline
+pub fn draw_rect(x: i32, y: i32, w: i32, h: i32, color: u8) {
+    draw_line(x, y, x + w - 1, y, color);
+    draw_line(x, y + h - 1, x + w - 1, y + h - 1, color);
+    draw_line(x, y, x, y + h - 1, color);
+    draw_line(x + w - 1, y, x + w - 1, y + h - 1, color);
+}
+
+// Plot the eight points symmetric about the circle's center
+fn draw_circle_points(xc: i32, yc: i32, x: i32, y: i32, color: u8) {
+    set_pixel(xc + x, yc + y, color);
+    set_pixel(xc - x, yc + y, color);
+    set_pixel(xc + x, yc - y, color);
+    set_pixel(xc - x, yc - y, color);
+    set_pixel(xc + y, yc + x, color);
+    set_pixel(xc - y, yc + x, color);
+    set_pixel(xc + y, yc - x, color);
+    set_pixel(xc - y, yc - x, color);
+}
+
+/// Draw a circle outline using the midpoint circle algorithm
+pub fn draw_circle(xc: i32, yc: i32, r: i32, color: u8) {
+    let mut x = r;
+    let mut y = 0;
+    let mut err = 0;
+
+    while x >= y {
+        draw_circle_points(xc, yc, x, y, color);
+
+        y += 1;
+        if err <= 0 {
+            err += 2 * y + 1;
+        }
+        if err > 0 {
+            x -= 1;
+            err -= 2 * x + 1;
+        }
+    }
+}
+
+/// Draw a filled circle using the midpoint circle algorithm
+pub fn fill_circle(xc: i32, yc: i32, r: i32, color: u8) {
+    let mut x = r;
+    let mut y = 0;
+    let mut err = 0;
+
+    while x >= y {
+        draw_line(xc - x, yc + y, xc + x, yc + y, color);
+        draw_line(xc - x, yc - y, xc + x, yc - y, color);
+        draw_line(xc - y, yc + x, xc + y, yc + x, color);
+        draw_line(xc - y, yc - x, xc + y, yc - x, color);
+
+        y += 1;
+        if err <= 0 {
+            err += 2 * y + 1;
+        }
+        if err > 0 {
+            x -= 1;
+            err -= 2 * x + 1;
+        }
+    }
+}
+
+/// Draw a triangle outline from its three vertices
+pub fn draw_triangle(x0: i32, y0: i32, x1: i32, y1: i32, x2: i32, y2: i32, color: u8) {
+    draw_line(x0, y0, x1, y1, color);
+    draw_line(x1, y1, x2, y2, color);
+    draw_line(x2, y2, x0, y0, color);
+}
+
+/// Draw a packed 1bpp, MSB-first bitmap at (x, y)
+pub fn draw_bitmap(x: i32, y: i32, data: &[u8], w: i32, h: i32, color: u8) {
+    if w <= 0 || h <= 0 {
+        return;
+    }
+
+    let stride = ((w + 7) / 8) as usize;
+
+    for j in 0..h {
+        for i in 0..w {
+            let byte_idx = (j as usize) * stride + (i as usize / 8);
+            if byte_idx >= data.len() {
+                continue;
+            }
+            let bit = 7 - (i % 8);
+            if (data[byte_idx] & (1 << bit)) != 0 {
+                set_pixel(x + i, y + j, color);
+            }
+        }
+    }
+}
+
 /// Set display brightness/contrast
 pub fn set_contrast(contrast: u8) {
-    send_command(SSD1306_CMD_SET_CONTRAST);
-    send_command(contrast);
+    send_commands(&[SSD1306_CMD_SET_CONTRAST, contrast]);
 }
 
 /// Turn display on or off